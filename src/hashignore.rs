@@ -0,0 +1,146 @@
+//! Minimal gitignore-style matcher for `.hashignore` files.
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug)]
+struct Rule {
+    glob: String,
+    negate: bool,
+    dir_only: bool,
+    anchored: bool,
+}
+
+impl Rule {
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let negate = line.starts_with('!');
+        let pat = if negate { &line[1..] } else { line };
+        let dir_only = pat.ends_with('/') && pat.len() > 1;
+        let pat = pat.strip_suffix('/').unwrap_or(pat);
+        let anchored = pat.contains('/');
+        let glob = pat.strip_prefix('/').unwrap_or(pat).to_owned();
+
+        Some(Self {
+            glob,
+            negate,
+            dir_only,
+            anchored,
+        })
+    }
+
+    fn matches(&self, rel_path: &str, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        if self.anchored {
+            return glob_match(self.glob.as_bytes(), rel_path.as_bytes());
+        }
+        let segments: Vec<&str> = rel_path.split('/').collect();
+        (0..segments.len()).any(|i| glob_match(self.glob.as_bytes(), segments[i..].join("/").as_bytes()))
+    }
+}
+
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match (pattern, text) {
+        ([], []) => true,
+        ([], _) => false,
+        ([b'*', b'*', rest @ ..], t) => {
+            glob_match(rest, t) || (!t.is_empty() && glob_match(pattern, &t[1..]))
+        }
+        ([b'*', rest @ ..], t) => {
+            glob_match(rest, t) || (!t.is_empty() && t[0] != b'/' && glob_match(pattern, &t[1..]))
+        }
+        ([pc, prest @ ..], [tc, trest @ ..]) if pc == tc => glob_match(prest, trest),
+        _ => false,
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct HashIgnore {
+    rules: Vec<Rule>,
+}
+
+impl HashIgnore {
+    pub const FILE_NAME: &'static str = ".hashignore";
+
+    pub fn load(dir: &Path) -> Self {
+        let rules = fs::read_to_string(dir.join(Self::FILE_NAME))
+            .map(|content| Self::parse(&content).rules)
+            .unwrap_or_default();
+        Self { rules }
+    }
+
+    fn parse(content: &str) -> Self {
+        Self {
+            rules: content.lines().filter_map(Rule::parse).collect(),
+        }
+    }
+
+    pub fn is_ignored(&self, rel_path: &str, is_dir: bool) -> bool {
+        let mut ignored = false;
+        for rule in &self.rules {
+            if rule.matches(rel_path, is_dir) {
+                ignored = !rule.negate;
+            }
+        }
+        ignored
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HashIgnore;
+
+    #[test]
+    fn comments_and_blank_lines_are_skipped() {
+        let ignore = HashIgnore::parse("# comment\n\n*.tmp\n");
+        assert!(ignore.is_ignored("scratch.tmp", false));
+        assert!(!ignore.is_ignored("# comment", false));
+    }
+
+    #[test]
+    fn unanchored_glob_matches_at_any_depth() {
+        let ignore = HashIgnore::parse("*.log");
+        assert!(ignore.is_ignored("debug.log", false));
+        assert!(ignore.is_ignored("nested/dir/debug.log", false));
+    }
+
+    #[test]
+    fn anchored_pattern_only_matches_from_root() {
+        let ignore = HashIgnore::parse("/build");
+        assert!(ignore.is_ignored("build", true));
+        assert!(!ignore.is_ignored("nested/build", true));
+    }
+
+    #[test]
+    fn trailing_slash_only_matches_directories() {
+        let ignore = HashIgnore::parse("cache/");
+        assert!(ignore.is_ignored("cache", true));
+        assert!(!ignore.is_ignored("cache", false));
+    }
+
+    #[test]
+    fn double_star_matches_across_path_segments() {
+        let ignore = HashIgnore::parse("assets/**/*.bin");
+        assert!(ignore.is_ignored("assets/bin/a.bin", false));
+        assert!(ignore.is_ignored("assets/a/b/c/a.bin", false));
+        assert!(!ignore.is_ignored("assets/a.bin.txt", false));
+    }
+
+    #[test]
+    fn later_negation_overrides_earlier_match() {
+        let ignore = HashIgnore::parse("*.log\n!keep.log\n");
+        assert!(ignore.is_ignored("debug.log", false));
+        assert!(!ignore.is_ignored("keep.log", false));
+    }
+
+    #[test]
+    fn later_rule_takes_precedence_over_earlier_negation() {
+        let ignore = HashIgnore::parse("!important.tmp\n*.tmp\n");
+        assert!(ignore.is_ignored("important.tmp", false));
+    }
+}
@@ -1,8 +1,14 @@
 use clap::{Arg, Command};
 use runner::{Error, Runner};
+#[cfg(target_os = "linux")]
+use runner::WatchMode;
 use std::path::PathBuf;
 
+#[cfg(target_os = "linux")]
+mod hashignore;
 mod runner;
+#[cfg(target_os = "linux")]
+mod sandbox;
 
 fn main() -> Result<(), Error> {
     let cmd = Command::new("hash")
@@ -26,13 +32,45 @@ fn main() -> Result<(), Error> {
                 .short('d')
                 .env("HASH_DECODER")
                 .default_value("cat")
-                .help("Script decoder"),
+                .help(
+                    "Script decoder: cat, base64, base32, hex, gpg, age:keyfile=<path>, or a shell command",
+                ),
             #[cfg(target_os = "linux")]
             Arg::new("watch")
                 .long("watch")
                 .short('w')
                 .num_args(0)
-                .help("Watch for removable media")
+                .help("Watch for removable media"),
+            Arg::new("keep-logs")
+                .long("keep-logs")
+                .short('k')
+                .num_args(0)
+                .help("Keep decode/stdout/stderr logs in the run directory"),
+            #[cfg(target_os = "linux")]
+            Arg::new("sandbox")
+                .long("sandbox")
+                .short('s')
+                .num_args(0)
+                .help("Run the script namespace-isolated with only the run directory writable"),
+            #[cfg(target_os = "linux")]
+            Arg::new("allow-net")
+                .long("allow-net")
+                .num_args(0)
+                .requires("sandbox")
+                .help("Keep network access inside the sandbox"),
+            #[cfg(target_os = "linux")]
+            Arg::new("watch-mode")
+                .long("watch-mode")
+                .value_parser(["mount", "content"])
+                .default_value("mount")
+                .requires("watch")
+                .help("Watch for a new mount, or for content changes in an already-mounted dir"),
+            #[cfg(target_os = "linux")]
+            Arg::new("debounce")
+                .long("debounce")
+                .value_parser(clap::value_parser!(u64))
+                .default_value("500")
+                .help("Content-watch debounce window in milliseconds"),
         ])
         .get_matches();
 
@@ -53,6 +91,32 @@ fn main() -> Result<(), Error> {
     let watch = cmd.get_flag("watch");
     #[cfg(not(target_os = "linux"))]
     let watch = false;
+    let keep_logs = cmd.get_flag("keep-logs");
+    #[cfg(target_os = "linux")]
+    let sandbox = cmd.get_flag("sandbox");
+    #[cfg(target_os = "linux")]
+    let allow_net = cmd.get_flag("allow-net");
+    #[cfg(target_os = "linux")]
+    let watch_mode = match cmd.get_one::<String>("watch-mode").map(String::as_str) {
+        Some("content") => WatchMode::Content,
+        _ => WatchMode::Mount,
+    };
+    #[cfg(target_os = "linux")]
+    let debounce_ms = *cmd.get_one::<u64>("debounce").expect("has default");
 
-    Runner::run(host_id, decoder, &path, watch)
+    Runner::run(
+        host_id,
+        decoder,
+        &path,
+        watch,
+        keep_logs,
+        #[cfg(target_os = "linux")]
+        sandbox,
+        #[cfg(target_os = "linux")]
+        allow_net,
+        #[cfg(target_os = "linux")]
+        watch_mode,
+        #[cfg(target_os = "linux")]
+        debounce_ms,
+    )
 }
@@ -5,15 +5,35 @@ use std::{
     process::{Child, Command, Stdio},
 };
 
+#[cfg(target_os = "linux")]
+use crate::hashignore::HashIgnore;
+#[cfg(target_os = "linux")]
+use crate::sandbox;
+
 pub const SCRIPT_SUFFIX: &str = ".ha.sh";
 pub const MAX_SCRIPT_SIZE: u64 = 655_360;
 
+/// Marker embedded in every `eval_script` run directory name
+/// (`<name>-run-<timestamp>`), used by the content watcher to ignore its
+/// own output instead of re-triggering on it.
+const RUN_DIR_MARKER: &str = "-run-";
+
+pub const BUNDLE_SUFFIX: &str = ".ha.tar.xz";
+pub const MAX_BUNDLE_SIZE: u64 = 16_777_216;
+pub const MAX_BUNDLE_EXPANDED_SIZE: u64 = 268_435_456;
+pub const BUNDLE_ENTRY: &str = "entry.ha.sh";
+const BUNDLE_DICT_SIZE: u32 = 64 * 1024 * 1024;
+
+const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE32_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
 #[derive(Debug)]
 pub enum Error {
     IO(io::Error),
     ScriptNotFound(PathBuf),
     UnsupportedScript(PathBuf),
     DecodeFailed(PathBuf),
+    DecryptFailed(PathBuf),
 }
 
 impl fmt::Display for Error {
@@ -21,30 +41,44 @@ impl fmt::Display for Error {
         match self {
             Self::IO(err) => write!(f, "IO: {}", err),
             Self::DecodeFailed(path) => write!(f, "Script decode failed: {:?}", path),
+            Self::DecryptFailed(path) => write!(f, "Script decryption failed: {:?}", path),
             Self::ScriptNotFound(path) => write!(f, "Script not found: {:?}", path),
             Self::UnsupportedScript(path) => write!(f, "Unsupported script: {:?}", path),
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScriptKind {
+    Plain,
+    Bundle,
+}
+
 #[derive(Debug)]
 pub struct Script {
     path: PathBuf,
+    kind: ScriptKind,
 }
 
 impl Script {
     pub fn from_file(path: &Path) -> Result<Self, Error> {
-        if path
-            .to_str()
-            .map(|p| !p.ends_with(SCRIPT_SUFFIX))
-            .unwrap_or_default()
-        {
-            Err(Error::UnsupportedScript(path.to_path_buf()))
-        } else if path.is_dir() {
-            Err(Error::ScriptNotFound(path.to_path_buf()))
-        } else {
-            let path = path.canonicalize().map_err(Error::IO)?;
-            Ok(Self { path })
+        let kind = path.to_str().and_then(|p| {
+            if p.ends_with(BUNDLE_SUFFIX) {
+                Some(ScriptKind::Bundle)
+            } else if p.ends_with(SCRIPT_SUFFIX) {
+                Some(ScriptKind::Plain)
+            } else {
+                None
+            }
+        });
+
+        match kind {
+            None => Err(Error::UnsupportedScript(path.to_path_buf())),
+            Some(_) if path.is_dir() => Err(Error::ScriptNotFound(path.to_path_buf())),
+            Some(kind) => {
+                let path = path.canonicalize().map_err(Error::IO)?;
+                Ok(Self { path, kind })
+            }
         }
     }
 
@@ -59,29 +93,196 @@ impl Script {
     }
 
     pub fn name(&self) -> String {
+        let suffix = match self.kind {
+            ScriptKind::Plain => SCRIPT_SUFFIX,
+            ScriptKind::Bundle => BUNDLE_SUFFIX,
+        };
         self.path
             .file_name()
             .and_then(|f| f.to_str().to_owned())
             .map(|f| f.to_owned())
             .unwrap_or_default()
-            .replace(SCRIPT_SUFFIX, "")
+            .replace(suffix, "")
+    }
+
+    fn max_size(&self) -> u64 {
+        match self.kind {
+            ScriptKind::Plain => MAX_SCRIPT_SIZE,
+            ScriptKind::Bundle => MAX_BUNDLE_SIZE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Decoder<'a> {
+    Cat,
+    Base64,
+    Base32,
+    Hex,
+    Age { keyfile: &'a str },
+    Gpg,
+    Shell(&'a str),
+}
+
+/// Parses `--decoder`. Built-in names and the `age:keyfile=...` decrypt
+/// envelope are handled in-process, `gpg` runs the `gpg` binary directly;
+/// anything else falls back to being run as a shell command, as before.
+fn parse_decoder(spec: &str) -> Decoder<'_> {
+    match spec {
+        "cat" => Decoder::Cat,
+        "base64" => Decoder::Base64,
+        "base32" => Decoder::Base32,
+        "hex" => Decoder::Hex,
+        "gpg" => Decoder::Gpg,
+        _ => match spec.strip_prefix("age:") {
+            Some(rest) => Decoder::Age {
+                keyfile: rest.strip_prefix("keyfile=").unwrap_or(rest),
+            },
+            None => Decoder::Shell(spec),
+        },
     }
 }
 
+fn decode_hex(input: &[u8]) -> Option<Vec<u8>> {
+    let digits: Vec<u8> = input
+        .iter()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+    if digits.len() % 2 != 0 {
+        return None;
+    }
+    let mut out = Vec::with_capacity(digits.len() / 2);
+    for pair in digits.chunks(2) {
+        let hi = (pair[0] as char).to_digit(16)?;
+        let lo = (pair[1] as char).to_digit(16)?;
+        out.push((hi << 4 | lo) as u8);
+    }
+    Some(out)
+}
+
+fn decode_base64(input: &[u8]) -> Option<Vec<u8>> {
+    let digits: Vec<u8> = input
+        .iter()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace() && *b != b'=')
+        .collect();
+    if digits.len() % 4 == 1 {
+        return None;
+    }
+    let mut out = Vec::with_capacity(digits.len() / 4 * 3);
+    for group in digits.chunks(4) {
+        let mut vals = [0u32; 4];
+        for (i, d) in group.iter().enumerate() {
+            vals[i] = BASE64_ALPHABET.iter().position(|c| c == d)? as u32;
+        }
+        let buf = vals[0] << 18 | vals[1] << 12 | vals[2] << 6 | vals[3];
+        out.push((buf >> 16) as u8);
+        if group.len() > 2 {
+            out.push((buf >> 8) as u8);
+        }
+        if group.len() > 3 {
+            out.push(buf as u8);
+        }
+    }
+    Some(out)
+}
+
+fn decode_base32(input: &[u8]) -> Option<Vec<u8>> {
+    let digits: Vec<u8> = input
+        .iter()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace() && *b != b'=')
+        .map(|b| b.to_ascii_uppercase())
+        .collect();
+    if digits.len() % 8 == 1 || digits.len() % 8 == 3 || digits.len() % 8 == 6 {
+        return None;
+    }
+    let mut out = Vec::with_capacity(digits.len() / 8 * 5);
+    for group in digits.chunks(8) {
+        let mut vals = [0u64; 8];
+        for (i, d) in group.iter().enumerate() {
+            vals[i] = BASE32_ALPHABET.iter().position(|c| c == d)? as u64;
+        }
+        let buf = vals[0] << 35
+            | vals[1] << 30
+            | vals[2] << 25
+            | vals[3] << 20
+            | vals[4] << 15
+            | vals[5] << 10
+            | vals[6] << 5
+            | vals[7];
+        let bytes = buf.to_be_bytes();
+        let out_len = match group.len() {
+            8 => 5,
+            7 => 4,
+            5 => 3,
+            4 => 2,
+            2 => 1,
+            _ => return None,
+        };
+        out.extend_from_slice(&bytes[3..3 + out_len]);
+    }
+    Some(out)
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchMode {
+    /// Fire when a new filesystem is mounted at `path` (removable media).
+    Mount,
+    /// Fire when the already-mounted `path` is quiet after a burst of writes.
+    Content,
+}
+
 #[derive(Debug)]
 pub struct Runner {
     host_id: String,
     decoder: String,
+    keep_logs: bool,
+    #[cfg(target_os = "linux")]
+    sandbox: bool,
+    #[cfg(target_os = "linux")]
+    allow_net: bool,
+    #[cfg(target_os = "linux")]
+    watch_mode: WatchMode,
+    #[cfg(target_os = "linux")]
+    debounce: std::time::Duration,
 }
 
 impl Runner {
-    pub fn run(host_id: String, decoder: String, path: &Path, watch: bool) -> Result<(), Error> {
-        let runner = Self { host_id, decoder };
+    pub fn run(
+        host_id: String,
+        decoder: String,
+        path: &Path,
+        watch: bool,
+        keep_logs: bool,
+        #[cfg(target_os = "linux")] sandbox: bool,
+        #[cfg(target_os = "linux")] allow_net: bool,
+        #[cfg(target_os = "linux")] watch_mode: WatchMode,
+        #[cfg(target_os = "linux")] debounce_ms: u64,
+    ) -> Result<(), Error> {
+        let runner = Self {
+            host_id,
+            decoder,
+            keep_logs,
+            #[cfg(target_os = "linux")]
+            sandbox,
+            #[cfg(target_os = "linux")]
+            allow_net,
+            #[cfg(target_os = "linux")]
+            watch_mode,
+            #[cfg(target_os = "linux")]
+            debounce: std::time::Duration::from_millis(debounce_ms),
+        };
         if path.is_file() {
             runner.eval_script(path)
         } else if watch {
             #[cfg(target_os = "linux")]
-            runner.watch(path)?;
+            match runner.watch_mode {
+                WatchMode::Mount => runner.watch(path)?,
+                WatchMode::Content => runner.watch_content(path)?,
+            }
             Ok(())
         } else {
             runner.eval_dir(path)
@@ -115,6 +316,64 @@ impl Runner {
         Ok(())
     }
 
+    #[cfg(target_os = "linux")]
+    fn watch_content(&self, path: &Path) -> Result<(), Error> {
+        use notify::{RecursiveMode, Watcher};
+        use std::sync::mpsc;
+
+        let ignore = HashIgnore::load(path);
+        let (tx, rx) = mpsc::channel::<notify::Event>();
+
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                tx.send(event).ok();
+            }
+        })
+        .map_err(|err| Error::IO(io::Error::other(err)))?;
+        watcher
+            .watch(path, RecursiveMode::Recursive)
+            .map_err(|err| Error::IO(io::Error::other(err)))?;
+
+        while let Ok(first) = rx.recv() {
+            let mut relevant = self.is_relevant(&first, path, &ignore);
+            loop {
+                match rx.recv_timeout(self.debounce) {
+                    Ok(ev) => relevant = relevant || self.is_relevant(&ev, path, &ignore),
+                    Err(mpsc::RecvTimeoutError::Timeout) => break,
+                    Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(()),
+                }
+            }
+            if relevant {
+                self.eval_dir(path).ok();
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    fn is_relevant(&self, event: &notify::Event, root: &Path, ignore: &HashIgnore) -> bool {
+        event.paths.iter().any(|p| {
+            let is_dir = p.is_dir();
+            match p.strip_prefix(root) {
+                Ok(rel) if !rel.as_os_str().is_empty() => rel
+                    .to_str()
+                    .map(|rel| !Self::is_own_run_dir(rel) && !ignore.is_ignored(rel, is_dir))
+                    .unwrap_or(true),
+                _ => false,
+            }
+        })
+    }
+
+    /// True if `rel` (relative to the watched root) falls under one of this
+    /// tool's own `<name>-run-<timestamp>` directories, so the watcher
+    /// doesn't re-trigger on the output it just produced.
+    #[cfg(target_os = "linux")]
+    fn is_own_run_dir(rel: &str) -> bool {
+        rel.split('/')
+            .next()
+            .is_some_and(|top| top.contains(RUN_DIR_MARKER))
+    }
+
     pub fn eval_dir(&self, dir: &Path) -> Result<(), Error> {
         let files = fs::read_dir(dir).map_err(Error::IO)?;
         let files: Vec<PathBuf> = files
@@ -149,8 +408,9 @@ impl Runner {
         let script = Script::from_file(path)?;
         let mut run_dir = script.parent()?.to_path_buf();
         run_dir.push(format!(
-            "{}-run-{}",
+            "{}{}{}",
             script.name(),
+            RUN_DIR_MARKER,
             Utc::now().format("%Y-%m-%d-%H-%M-%S")
         ));
         fs::create_dir(&run_dir).map_err(Error::IO)?;
@@ -165,29 +425,54 @@ impl Runner {
         Ok(())
     }
 
-    fn spawn(&self, script: &Script, run_dir: &Path) -> Result<Child, Error> {
-        let script_len = fs::metadata(&script.path).map_err(Error::IO)?.len();
-        if script_len > MAX_SCRIPT_SIZE {
-            return Err(Error::UnsupportedScript(script.path.to_path_buf()));
-        }
-
+    fn decode_script(&self, script: &Script, run_dir: &Path) -> Result<String, Error> {
         let mut script_file = fs::File::open(&script.path).map_err(Error::IO)?;
-        let mut buf = Vec::new();
+        let mut raw = Vec::new();
+        io::copy(&mut script_file, &mut raw).map_err(Error::IO)?;
+
+        let buf = match parse_decoder(&self.decoder) {
+            Decoder::Cat => raw,
+            Decoder::Base64 => {
+                decode_base64(&raw).ok_or(Error::DecodeFailed(script.path.to_path_buf()))?
+            }
+            Decoder::Base32 => {
+                decode_base32(&raw).ok_or(Error::DecodeFailed(script.path.to_path_buf()))?
+            }
+            Decoder::Hex => decode_hex(&raw).ok_or(Error::DecodeFailed(script.path.to_path_buf()))?,
+            Decoder::Age { keyfile } => self.decrypt_age(script, &raw, keyfile)?,
+            Decoder::Gpg => self.decrypt_gpg(script, run_dir, &raw)?,
+            Decoder::Shell(cmd) => self.run_shell_decoder(script, run_dir, cmd, &raw)?,
+        };
+
+        str::from_utf8(&buf)
+            .map(|s| s.to_owned())
+            .map_err(|_| Error::UnsupportedScript(script.path.to_path_buf()))
+    }
 
+    fn run_shell_decoder(
+        &self,
+        script: &Script,
+        run_dir: &Path,
+        cmd: &str,
+        raw: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        let decode_log = self.log_stdio(run_dir, "decode.log")?;
         let mut decoder = Command::new("sh")
-            .args(["-c", &self.decoder])
+            .args(["-c", cmd])
             .current_dir(run_dir)
             .env("HASH_SCRIPT", script.path())
             .env("HASH_HOST", &self.host_id)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
+            .stderr(decode_log)
             .spawn()
             .map_err(Error::IO)?;
 
         if let Some(stdin) = &mut decoder.stdin {
-            io::copy(&mut script_file, stdin).map_err(Error::IO)?;
+            io::copy(&mut io::Cursor::new(raw), stdin).map_err(Error::IO)?;
         }
 
+        let mut buf = Vec::new();
         if decoder.wait().map_err(Error::IO)?.success() {
             if let Some(mut stdout) = decoder.stdout {
                 io::copy(&mut stdout, &mut buf).map_err(Error::IO)?;
@@ -195,20 +480,219 @@ impl Runner {
         } else {
             return Err(Error::DecodeFailed(script.path.to_path_buf()));
         }
+        Ok(buf)
+    }
 
-        let script_text = str::from_utf8(&buf)
-            .map(|s| s.to_owned())
-            .map_err(|_| Error::UnsupportedScript(script.path.to_path_buf()))?;
-        let run_dir = run_dir.to_str().unwrap_or_default().to_owned();
+    /// Decrypts an age-encrypted script. The host id doubles as a
+    /// key-selection hint: a keyfile named `<keyfile>.<host_id>` is
+    /// preferred over the bare `keyfile` when present, so a stick can carry
+    /// per-host identities without the caller juggling paths.
+    fn decrypt_age(&self, script: &Script, raw: &[u8], keyfile: &str) -> Result<Vec<u8>, Error> {
+        use age::{Decryptor, Identity};
+        use std::io::Read;
+
+        let hinted = format!("{}.{}", keyfile, self.host_id);
+        let keyfile = if Path::new(&hinted).is_file() {
+            hinted
+        } else {
+            keyfile.to_owned()
+        };
 
-        Command::new("sh")
-            .args(["-c", &script_text])
+        let fail = || Error::DecryptFailed(script.path.to_path_buf());
+        let identities = age::IdentityFile::from_file(keyfile)
+            .and_then(|f| f.into_identities())
+            .map_err(|_| fail())?;
+
+        let mut reader = match Decryptor::new(raw).map_err(|_| fail())? {
+            Decryptor::Recipients(d) => d
+                .decrypt(identities.iter().map(|i| i.as_ref() as &dyn Identity))
+                .map_err(|_| fail())?,
+            Decryptor::Passphrase(_) => return Err(fail()),
+        };
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).map_err(|_| fail())?;
+        Ok(buf)
+    }
+
+    fn decrypt_gpg(&self, script: &Script, run_dir: &Path, raw: &[u8]) -> Result<Vec<u8>, Error> {
+        let decode_log = self.log_stdio(run_dir, "decode.log")?;
+        let mut gpg = Command::new("gpg")
+            .args(["--batch", "--yes", "--decrypt"])
             .current_dir(run_dir)
             .env("HASH_SCRIPT", script.path())
             .env("HASH_HOST", &self.host_id)
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(decode_log)
             .spawn()
-            .map_err(Error::IO)
+            .map_err(Error::IO)?;
+
+        if let Some(stdin) = &mut gpg.stdin {
+            io::copy(&mut io::Cursor::new(raw), stdin).map_err(Error::IO)?;
+        }
+
+        let mut buf = Vec::new();
+        if gpg.wait().map_err(Error::IO)?.success() {
+            if let Some(mut stdout) = gpg.stdout {
+                io::copy(&mut stdout, &mut buf).map_err(Error::IO)?;
+            }
+        } else {
+            return Err(Error::DecryptFailed(script.path.to_path_buf()));
+        }
+        Ok(buf)
+    }
+
+    fn extract_bundle(&self, script: &Script, run_dir: &Path) -> Result<String, Error> {
+        let file = fs::File::open(&script.path).map_err(Error::IO)?;
+        let stream = xz2::stream::Stream::new_stream_decoder(BUNDLE_DICT_SIZE as u64, 0)
+            .map_err(|_| Error::UnsupportedScript(script.path.to_path_buf()))?;
+        let mut archive = tar::Archive::new(xz2::read::XzDecoder::new_stream(file, stream));
+
+        let mut entry_path = None;
+        let mut expanded_size = 0u64;
+        for entry in archive.entries().map_err(Error::IO)? {
+            let mut entry = entry.map_err(Error::IO)?;
+
+            if matches!(
+                entry.header().entry_type(),
+                tar::EntryType::Symlink | tar::EntryType::Link
+            ) {
+                return Err(Error::UnsupportedScript(script.path.to_path_buf()));
+            }
+
+            expanded_size += entry.header().size().map_err(Error::IO)?;
+            if expanded_size > MAX_BUNDLE_EXPANDED_SIZE {
+                return Err(Error::UnsupportedScript(script.path.to_path_buf()));
+            }
+
+            let rel_path = entry.path().map_err(Error::IO)?.to_path_buf();
+            if rel_path
+                .components()
+                .any(|c| matches!(c, std::path::Component::ParentDir | std::path::Component::RootDir))
+            {
+                return Err(Error::UnsupportedScript(script.path.to_path_buf()));
+            }
+
+            let dest = run_dir.join(&rel_path);
+            entry.unpack(&dest).map_err(Error::IO)?;
+            if rel_path == Path::new(BUNDLE_ENTRY) {
+                entry_path = Some(dest);
+            }
+        }
+
+        let entry_path = entry_path.ok_or(Error::UnsupportedScript(script.path.to_path_buf()))?;
+        fs::read_to_string(entry_path).map_err(Error::IO)
+    }
+
+    fn spawn(&self, script: &Script, run_dir: &Path) -> Result<Child, Error> {
+        let script_len = fs::metadata(&script.path).map_err(Error::IO)?.len();
+        if script_len > script.max_size() {
+            return Err(Error::UnsupportedScript(script.path.to_path_buf()));
+        }
+
+        let script_text = match script.kind {
+            ScriptKind::Bundle => self.extract_bundle(script, run_dir)?,
+            ScriptKind::Plain => self.decode_script(script, run_dir)?,
+        };
+
+        if self.keep_logs {
+            fs::write(run_dir.join("script.decoded"), &script_text).map_err(Error::IO)?;
+        }
+
+        let stdout_log = self.log_stdio(run_dir, "stdout.log")?;
+        let stderr_log = self.log_stdio(run_dir, "stderr.log")?;
+
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", &script_text])
+            .current_dir(run_dir)
+            .env("HASH_SCRIPT", script.path())
+            .env("HASH_HOST", &self.host_id)
+            .stdout(stdout_log)
+            .stderr(stderr_log);
+
+        #[cfg(target_os = "linux")]
+        if self.sandbox {
+            sandbox::confine(&mut cmd, run_dir, self.allow_net);
+        }
+
+        cmd.spawn().map_err(Error::IO)
+    }
+
+    fn log_stdio(&self, run_dir: &Path, file_name: &str) -> Result<Stdio, Error> {
+        if self.keep_logs {
+            fs::File::create(run_dir.join(file_name))
+                .map(Stdio::from)
+                .map_err(Error::IO)
+        } else {
+            Ok(Stdio::null())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_base32, decode_base64, decode_hex};
+
+    #[test]
+    fn hex_decodes_pairs() {
+        assert_eq!(decode_hex(b"68656c6c6f").unwrap(), b"hello");
+    }
+
+    #[test]
+    fn hex_ignores_whitespace() {
+        assert_eq!(decode_hex(b" 68 65\n6c 6c 6f ").unwrap(), b"hello");
+    }
+
+    #[test]
+    fn hex_rejects_odd_length() {
+        assert_eq!(decode_hex(b"68656c6c6"), None);
+    }
+
+    #[test]
+    fn hex_rejects_invalid_symbols() {
+        assert_eq!(decode_hex(b"zz"), None);
+    }
+
+    #[test]
+    fn base64_decodes_full_quanta() {
+        assert_eq!(decode_base64(b"aGVsbG8h").unwrap(), b"hello!");
+    }
+
+    #[test]
+    fn base64_strips_padding_and_whitespace() {
+        assert_eq!(decode_base64(b"aGVsbG8=\n").unwrap(), b"hello");
+        assert_eq!(decode_base64(b"aGVsbG8").unwrap(), b"hello");
+    }
+
+    #[test]
+    fn base64_rejects_truncated_group() {
+        assert_eq!(decode_base64(b"a"), None);
+    }
+
+    #[test]
+    fn base64_rejects_invalid_symbols() {
+        assert_eq!(decode_base64(b"!!!!"), None);
+    }
+
+    #[test]
+    fn base32_decodes_full_quanta() {
+        assert_eq!(decode_base32(b"NBSWY3DP").unwrap(), b"hello");
+    }
+
+    #[test]
+    fn base32_is_case_insensitive_and_strips_padding() {
+        assert_eq!(decode_base32(b"nbswy3dp").unwrap(), b"hello");
+        assert_eq!(decode_base32(b"NBSWY3A=").unwrap(), b"hell");
+    }
+
+    #[test]
+    fn base32_rejects_truncated_group() {
+        assert_eq!(decode_base32(b"N"), None);
+    }
+
+    #[test]
+    fn base32_rejects_invalid_symbols() {
+        assert_eq!(decode_base32(b"01189998"), None);
     }
 }
@@ -0,0 +1,174 @@
+//! Linux-only process isolation for untrusted scripts read off removable media.
+#![cfg(target_os = "linux")]
+
+use nix::mount::{mount, umount2, MntFlags, MsFlags};
+use nix::sched::{unshare, CloneFlags};
+use nix::sys::wait::{waitpid, WaitStatus};
+use nix::unistd::{chdir, fork, getegid, geteuid, pivot_root, ForkResult};
+use std::fs;
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const CAPSET_DROP_MAX: i32 = 63;
+const LINUX_CAPABILITY_VERSION_3: u32 = 0x2008_0522;
+
+#[repr(C)]
+struct CapUserHeader {
+    version: u32,
+    pid: i32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+struct CapUserData {
+    effective: u32,
+    permitted: u32,
+    inheritable: u32,
+}
+
+/// Arms `cmd` to unshare namespaces and `pivot_root` into a tmpfs right before
+/// `exec`, with `run_dir` bind-mounted at `/workspace` as the working directory.
+///
+/// The script ends up with `run_dir` as its only writable path, `/usr`, `/bin`
+/// and `/lib` mounted read-only for its own use, and no network unless
+/// `allow_net` is set.
+pub fn confine(cmd: &mut Command, run_dir: &Path, allow_net: bool) {
+    let run_dir = run_dir.to_path_buf();
+    let uid = geteuid();
+    let gid = getegid();
+
+    unsafe {
+        cmd.pre_exec(move || {
+            enter(&run_dir, uid.as_raw(), gid.as_raw(), allow_net)
+                .map_err(|err| std::io::Error::from_raw_os_error(err as i32))
+        });
+    }
+}
+
+fn enter(run_dir: &Path, uid: u32, gid: u32, allow_net: bool) -> nix::Result<()> {
+    let mut flags = CloneFlags::CLONE_NEWNS
+        | CloneFlags::CLONE_NEWPID
+        | CloneFlags::CLONE_NEWUSER;
+    if !allow_net {
+        flags |= CloneFlags::CLONE_NEWNET;
+    }
+    unshare(flags)?;
+
+    fs::write("/proc/self/setgroups", "deny").ok();
+    fs::write("/proc/self/uid_map", format!("0 {} 1", uid)).ok();
+    fs::write("/proc/self/gid_map", format!("0 {} 1", gid)).ok();
+
+    // CLONE_NEWPID only places *future children* into the new PID namespace,
+    // not the unsharing process itself, so fork here: the child becomes PID 1
+    // of the new namespace and goes on to set up mounts and exec the script,
+    // while this process just waits for it and mirrors its exit status.
+    match unsafe { fork()? } {
+        ForkResult::Parent { child } => {
+            let code = match waitpid(child, None)? {
+                WaitStatus::Exited(_, code) => code,
+                WaitStatus::Signaled(_, signal, _) => 128 + signal as i32,
+                _ => 1,
+            };
+            std::process::exit(code);
+        }
+        ForkResult::Child => {}
+    }
+
+    mount(
+        None::<&str>,
+        "/",
+        None::<&str>,
+        MsFlags::MS_REC | MsFlags::MS_PRIVATE,
+        None::<&str>,
+    )?;
+
+    let new_root = run_dir.join(".hash-sandbox");
+    fs::create_dir_all(&new_root).ok();
+    mount(
+        Some("tmpfs"),
+        &new_root,
+        Some("tmpfs"),
+        MsFlags::empty(),
+        None::<&str>,
+    )?;
+
+    for ro in ["usr", "bin", "lib"] {
+        let source = PathBuf::from("/").join(ro);
+        let target = new_root.join(ro);
+        fs::create_dir_all(&target).ok();
+        mount(
+            Some(&source),
+            &target,
+            None::<&str>,
+            MsFlags::MS_BIND | MsFlags::MS_REC,
+            None::<&str>,
+        )?;
+        mount(
+            None::<&str>,
+            &target,
+            None::<&str>,
+            MsFlags::MS_BIND | MsFlags::MS_REMOUNT | MsFlags::MS_RDONLY | MsFlags::MS_REC,
+            None::<&str>,
+        )?;
+    }
+
+    let workspace = new_root.join("workspace");
+    fs::create_dir_all(&workspace).ok();
+    mount(
+        Some(run_dir),
+        &workspace,
+        None::<&str>,
+        MsFlags::MS_BIND,
+        None::<&str>,
+    )?;
+
+    // `.old-root` must be created, and pivot_root must happen, while
+    // `new_root` is still writable — only lock it down afterwards.
+    let old_root = new_root.join(".old-root");
+    fs::create_dir_all(&old_root).ok();
+    pivot_root(&new_root, &old_root)?;
+    chdir("/workspace")?;
+    umount2("/.old-root", MntFlags::MNT_DETACH)?;
+    fs::remove_dir("/.old-root").ok();
+
+    // Lock the new root itself read-only. This is a non-recursive remount,
+    // so it leaves /workspace (bind-mounted from run_dir) writable.
+    mount(
+        None::<&str>,
+        "/",
+        None::<&str>,
+        MsFlags::MS_REMOUNT | MsFlags::MS_RDONLY,
+        None::<&str>,
+    )?;
+
+    drop_all_capabilities();
+    Ok(())
+}
+
+/// Drops every capability in the bounding, effective, permitted, inheritable
+/// and ambient sets, so the script runs fully unprivileged even though
+/// `CLONE_NEWUSER` grants it a full capability set inside its own namespace.
+fn drop_all_capabilities() {
+    for cap in 0..=CAPSET_DROP_MAX {
+        unsafe {
+            libc::prctl(libc::PR_CAPBSET_DROP, cap, 0, 0, 0);
+        }
+    }
+    unsafe {
+        libc::prctl(libc::PR_CAP_AMBIENT, libc::PR_CAP_AMBIENT_CLEAR_ALL, 0, 0, 0);
+    }
+
+    let header = CapUserHeader {
+        version: LINUX_CAPABILITY_VERSION_3,
+        pid: 0,
+    };
+    let data = [CapUserData::default(); 2];
+    unsafe {
+        libc::syscall(
+            libc::SYS_capset,
+            &header as *const CapUserHeader,
+            data.as_ptr(),
+        );
+    }
+}